@@ -0,0 +1,63 @@
+// Compares `step` with `step_reward_only` against a local mock server whose
+// observations have 100k elements, as for an image environment. Run with
+// `cargo bench --no-default-features`.
+extern crate gym;
+extern crate hyper;
+
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use gym::GymClient;
+use hyper::server::{Request, Response, Server};
+use hyper::uri::RequestUri;
+
+const OBSERVATION_LEN: usize = 100000;
+const STEPS: u32 = 100;
+
+fn main() {
+	let observation = vec!["0.123456789"; OBSERVATION_LEN].join(", ");
+	let step = format!(r#"{{"observation": [{}], "reward": 1.0, "done": false, "info": {{}}}}"#, observation);
+	let reset = format!(r#"{{"observation": [{}]}}"#, observation);
+
+	let server = Server::http("127.0.0.1:0").unwrap();
+	let mut listening = server.handle(move |mut req: Request, res: Response| {
+		let route = match req.uri {
+			RequestUri::AbsolutePath(ref path) => path.clone(),
+			_ => String::new()
+		};
+		let _ = req.read_to_end(&mut Vec::new());
+		let reply = match &route[..] {
+			"/v1/envs/" => r#"{"instance_id": "bench"}"#,
+			"/v1/envs/bench/action_space/" => r#"{"info": {"name": "Discrete", "n": 2}}"#,
+			"/v1/envs/bench/observation_space/" => r#"{"info": {"name": "Discrete", "n": 2}}"#,
+			"/v1/envs/bench/reset/" => &reset,
+			_ => &step
+		};
+		let _ = res.send(reply.as_bytes());
+	}).unwrap();
+
+	let mut env = GymClient::new(format!("http://{}", listening.socket)).make("Bench-v0").unwrap();
+	env.reset().unwrap();
+	let full = time(|| { env.step(vec![0.], false).unwrap(); });
+	let reward_only = time(|| { env.step_reward_only(vec![0.], false).unwrap(); });
+	println!("step:             {:>8.2}ms per step", millis(full));
+	println!("step_reward_only: {:>8.2}ms per step", millis(reward_only));
+	println!("speedup:          {:>8.2}x", full.as_secs_f64()/reward_only.as_secs_f64());
+	let _ = listening.close();
+}
+
+// The average time of a step, after a few to warm up.
+fn time<F: FnMut()>(mut step: F) -> Duration {
+	for _ in 0..5 {
+		step();
+	}
+	let start = Instant::now();
+	for _ in 0..STEPS {
+		step();
+	}
+	start.elapsed()/STEPS
+}
+
+fn millis(d: Duration) -> f64 {
+	d.as_secs_f64()*1000.
+}
@@ -5,7 +5,7 @@ use gym::*;
 fn main() {
 	println!("**********************************");
 
-	let mut client = GymClient::new("http://localhost:5000".to_string());
+	let client = GymClient::local();
 	//println!("already running environments:\n{:?}\n", client.get_envs().unwrap());
 
 	let mut env = match client.make("CartPole-v0") {
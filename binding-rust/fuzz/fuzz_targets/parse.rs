@@ -0,0 +1,34 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate gym;
+extern crate serde_json;
+
+use gym::{Space, State};
+use serde_json::Value;
+
+// Parsing any server answer may fail, but must never panic.
+fuzz_target!(|data: &[u8]| {
+	let json: Value = match std::str::from_utf8(data).ok().and_then(|s| serde_json::from_str(s).ok()) {
+		Some(json) => json,
+		None => return
+	};
+
+	// Whatever `make` accepts must also be safe to use.
+	let parsed = Space::from_json(&json).ok().into_iter().chain(json.find("info").and_then(|info| Space::from_json(info).ok()));
+	for space in parsed {
+		let dim = space.flat_dim();
+		let _ = space.check_action(&[0.; 4]);
+		if dim <= 1 << 16 {
+			let _ = space.check_action(&vec![0.; dim]);
+		}
+	}
+
+	let spaces = [
+		Space::DISCRETE{n: 4, labels: None},
+		Space::BOX{shape: vec![2], high: vec![1.; 2], low: vec![-1.; 2], dtype: "float32".to_string()},
+	];
+	for space in &spaces {
+		let _ = State::from_json(&json, space);
+	}
+});
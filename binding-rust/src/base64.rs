@@ -0,0 +1,45 @@
+const ALPHABET: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn encode(data: &[u8]) -> String {
+	let mut ret = String::with_capacity(data.len().div_ceil(3)*4);
+	for chunk in data.chunks(3) {
+		let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+		let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+		for i in 0..4 {
+			if i <= chunk.len() {
+				ret.push(ALPHABET[(n >> (18 - 6*i)) & 63] as char);
+			} else {
+				ret.push('=');
+			}
+		}
+	}
+	ret
+}
+
+// The number of bytes `decode` gives for `text`, if it is base64.
+pub fn decoded_len(text: &str) -> usize {
+	text.trim_end_matches('=').len()*3/4
+}
+
+// Decodes standard, padded or unpadded base64, or gives None if `text` is
+// not base64.
+pub fn decode(text: &str) -> Option<Vec<u8>> {
+	let text = text.trim_end_matches('=').as_bytes();
+	if text.len() % 4 == 1 {
+		return None;
+	}
+	let mut ret = Vec::with_capacity(text.len()*3/4);
+	for chunk in text.chunks(4) {
+		let mut n = 0;
+		for (i, &c) in chunk.iter().enumerate() {
+			match ALPHABET.iter().position(|&a| a == c) {
+				Some(digit) => n |= digit << (18 - 6*i),
+				None => return None
+			}
+		}
+		for i in 0..chunk.len() - 1 {
+			ret.push((n >> (16 - 8*i)) as u8);
+		}
+	}
+	Some(ret)
+}
@@ -0,0 +1,19 @@
+use hyper;
+use hyper::net::{HttpConnector, HttpStream, NetworkConnector};
+
+/// hyper's plain `HttpConnector`, with Nagle's algorithm switched on or off
+/// on every new connection.
+#[derive(Debug, Clone)]
+pub struct NoDelayConnector {
+	pub nodelay:	bool,
+}
+
+impl NetworkConnector for NoDelayConnector {
+	type Stream = HttpStream;
+
+	fn connect(&self, host: &str, port: u16, scheme: &str) -> hyper::Result<HttpStream> {
+		let stream = try!(HttpConnector.connect(host, port, scheme));
+		try!(stream.0.set_nodelay(self.nodelay));
+		Ok(stream)
+	}
+}
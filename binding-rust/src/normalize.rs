@@ -0,0 +1,92 @@
+/// Running per-dimension mean and variance of a stream of observations,
+/// maintained with Welford's algorithm.
+#[derive(Debug, Clone)]
+pub struct RunningNormalizer {
+	count:	u64,
+	mean:	Vec<f64>,
+	m2:		Vec<f64>,
+}
+
+const EPSILON: f64 = 1e-8;
+
+impl RunningNormalizer {
+	pub fn new(dim: usize) -> RunningNormalizer {
+		RunningNormalizer{count: 0, mean: vec![0.; dim], m2: vec![0.; dim]}
+	}
+	pub fn update(&mut self, obs: &[f64]) {
+		assert_eq!(obs.len(), self.mean.len());
+		self.count += 1;
+		let n = self.count as f64;
+		for (i, &x) in obs.iter().enumerate() {
+			let delta = x - self.mean[i];
+			self.mean[i] += delta/n;
+			self.m2[i] += delta*(x - self.mean[i]);
+		}
+	}
+	pub fn count(&self) -> u64 {
+		self.count
+	}
+	pub fn mean(&self) -> &[f64] {
+		&self.mean
+	}
+	/// Population variance of the observations seen so far.
+	pub fn variance(&self) -> Vec<f64> {
+		if self.count == 0 {
+			return vec![0.; self.m2.len()];
+		}
+		self.m2.iter().map(|&m2| m2/self.count as f64).collect()
+	}
+	/// Standardizes `obs` to zero mean and unit variance. Returns it unchanged
+	/// until the first update.
+	pub fn normalize(&self, obs: &[f64]) -> Vec<f64> {
+		assert_eq!(obs.len(), self.mean.len());
+		if self.count == 0 {
+			return obs.to_vec();
+		}
+		obs.iter().zip(self.mean.iter().zip(self.variance()))
+		   .map(|(&x, (&mean, var))| (x - mean)/(var + EPSILON).sqrt())
+		   .collect()
+	}
+}
+
+/// Scales rewards by the running standard deviation of the discounted
+/// return, as in the usual reward normalization wrapper. Only the scale
+/// changes; rewards are not shifted by a mean.
+#[derive(Debug, Clone)]
+pub struct RewardNormalizer {
+	gamma:		f64,
+	ret:		f64,
+	returns:	RunningNormalizer,
+}
+
+impl RewardNormalizer {
+	pub fn new(gamma: f64) -> RewardNormalizer {
+		assert!(0. <= gamma && gamma <= 1., "gamma must be in [0, 1], got {}", gamma);
+		RewardNormalizer{gamma: gamma, ret: 0., returns: RunningNormalizer::new(1)}
+	}
+	/// Folds `reward` into the discounted return of the current episode,
+	/// updates the return statistics and returns `reward` divided by their
+	/// standard deviation. Rewards are returned unchanged while that is still
+	/// zero. `done` restarts the return for the next call.
+	pub fn normalize(&mut self, reward: f64, done: bool) -> f64 {
+		self.ret = self.ret*self.gamma + reward;
+		self.returns.update(&[self.ret]);
+		if done {
+			self.ret = 0.;
+		}
+
+		let var = self.returns.variance()[0];
+		if var == 0. {
+			reward
+		} else {
+			reward/(var + EPSILON).sqrt()
+		}
+	}
+	/// The discounted return accumulated so far in the current episode.
+	pub fn discounted_return(&self) -> f64 {
+		self.ret
+	}
+	pub fn count(&self) -> u64 {
+		self.returns.count()
+	}
+}
@@ -0,0 +1,121 @@
+use std::collections::VecDeque;
+
+use {Space, finite_bound};
+
+enum Stage {
+	Clamp,
+	Normalize,
+	OneHot,
+	Stack{frames: usize, history: VecDeque<Vec<f64>>},
+	Custom(Box<dyn FnMut(&mut Vec<f64>, &Space)>),
+}
+
+/// A chain of observation transforms, applied in the order they were added.
+///
+/// `clamp` and `normalize` read the bounds of a `Space::BOX` and leave other
+/// spaces untouched, so they should come before stages that change the
+/// observation's length (`one_hot`, `stack`).
+pub struct ObsPipeline {
+	stages: Vec<Stage>,
+}
+
+impl Default for ObsPipeline {
+	fn default() -> ObsPipeline {
+		ObsPipeline::new()
+	}
+}
+
+impl ObsPipeline {
+	pub fn new() -> ObsPipeline {
+		ObsPipeline{stages: Vec::new()}
+	}
+	/// Clamps each element into the `[low, high]` bounds of a Box space.
+	pub fn clamp(mut self) -> ObsPipeline {
+		self.stages.push(Stage::Clamp);
+		self
+	}
+	/// Rescales each element with finite bounds from `[low, high]` to `[0, 1]`.
+	pub fn normalize(mut self) -> ObsPipeline {
+		self.stages.push(Stage::Normalize);
+		self
+	}
+	/// Replaces a Discrete observation `[k]` by a one-hot vector of length `n`.
+	pub fn one_hot(mut self) -> ObsPipeline {
+		self.stages.push(Stage::OneHot);
+		self
+	}
+	/// Concatenates the last `frames` observations, oldest first. Until enough
+	/// frames have been seen the oldest available one is repeated.
+	pub fn stack(mut self, frames: usize) -> ObsPipeline {
+		assert!(frames > 0, "cannot stack zero frames");
+		self.stages.push(Stage::Stack{frames: frames, history: VecDeque::with_capacity(frames)});
+		self
+	}
+	pub fn custom<F>(mut self, f: F) -> ObsPipeline where F: FnMut(&mut Vec<f64>, &Space) + 'static {
+		self.stages.push(Stage::Custom(Box::new(f)));
+		self
+	}
+	pub fn len(&self) -> usize {
+		self.stages.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.stages.is_empty()
+	}
+	/// Forgets the frame history of any `stack` stage. Call this on reset.
+	pub fn reset(&mut self) {
+		for stage in &mut self.stages {
+			if let Stage::Stack{ref mut history, ..} = *stage {
+				history.clear();
+			}
+		}
+	}
+	pub fn apply(&mut self, obs: &mut Vec<f64>, space: &Space) {
+		for stage in &mut self.stages {
+			match *stage {
+				Stage::Clamp => {
+					if let Space::BOX{ref high, ref low, ..} = *space {
+						for (x, (&lo, &hi)) in obs.iter_mut().zip(low.iter().zip(high)) {
+							*x = x.max(lo).min(hi);
+						}
+					}
+				},
+				Stage::Normalize => {
+					if let Space::BOX{ref high, ref low, ..} = *space {
+						for (x, (&lo, &hi)) in obs.iter_mut().zip(low.iter().zip(high)) {
+							if finite_bound(lo) && finite_bound(hi) && hi > lo {
+								*x = (*x - lo)/(hi - lo);
+							}
+						}
+					}
+				},
+				Stage::OneHot => {
+					if let Space::DISCRETE{n, ..} = *space {
+						let mut encoded = vec![0.; n as usize];
+						if let Some(&k) = obs.first() {
+							if k >= 0. && (k as u64) < n {
+								encoded[k as usize] = 1.;
+							}
+						}
+						*obs = encoded;
+					}
+				},
+				Stage::Stack{frames, ref mut history} => {
+					if history.len() == frames {
+						history.pop_front();
+					}
+					history.push_back(obs.clone());
+
+					let mut stacked = Vec::with_capacity(frames*obs.len());
+					for _ in history.len()..frames {
+						stacked.extend_from_slice(&history[0]);
+					}
+					for frame in history.iter() {
+						stacked.extend_from_slice(frame);
+					}
+					*obs = stacked;
+				},
+				Stage::Custom(ref mut f) => f(obs, space)
+			}
+		}
+	}
+}
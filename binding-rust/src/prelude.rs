@@ -0,0 +1,4 @@
+pub use serde_json::value::ToJson;
+
+pub use {ActionValue, Environment, GymClient, GymError, GymResult, Space, State, StepRequest};
+pub use {ObsPipeline, RetryPolicy, Trajectory, VectorEnvironment, WrappedEnvironment, WrapperSpec};
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+/// How often and how patiently `GymClient` repeats a request that failed
+/// with a transient error (see `GymError::is_transient`). The wait before
+/// the i-th retry is `initial_backoff * 2^i`, or what a 429 response asked
+/// for in its `Retry-After` header, and is capped at `max_backoff` either way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+	pub max_retries:		u32,
+	pub initial_backoff:	Duration,
+	pub max_backoff:		Duration,
+}
+
+impl RetryPolicy {
+	/// Never retries. The default.
+	pub fn none() -> RetryPolicy {
+		RetryPolicy{max_retries: 0, initial_backoff: Duration::from_millis(100), max_backoff: Duration::from_secs(5)}
+	}
+	pub fn new(max_retries: u32, initial_backoff: Duration) -> RetryPolicy {
+		RetryPolicy{max_retries: max_retries, initial_backoff: initial_backoff, ..RetryPolicy::none()}
+	}
+	pub fn backoff(&self, retry: u32) -> Duration {
+		let backoff = self.initial_backoff.checked_mul(1 << retry.min(30)).unwrap_or(self.max_backoff);
+		backoff.min(self.max_backoff)
+	}
+}
+
+impl Default for RetryPolicy {
+	fn default() -> RetryPolicy {
+		RetryPolicy::none()
+	}
+}
@@ -0,0 +1,38 @@
+/// Discounted rewards-to-go: `ret[t] = rewards[t] + gamma*ret[t+1]`.
+pub fn discounted_returns(rewards: &[f64], gamma: f64) -> Vec<f64> {
+	let mut ret = vec![0.; rewards.len()];
+	let mut running = 0.;
+	for t in (0..rewards.len()).rev() {
+		running = rewards[t] + gamma*running;
+		ret[t] = running;
+	}
+	ret
+}
+
+/// Generalized advantage estimates.
+///
+/// `values[t]` is the value estimate of the state in which `rewards[t]` was
+/// received. `values` may hold one extra trailing element, the bootstrap value
+/// of the state following the last step; otherwise that value is taken as 0.
+/// `dones[t]` marks the last step of an episode, past which nothing is
+/// bootstrapped or accumulated.
+pub fn gae(rewards: &[f64], values: &[f64], gamma: f64, lambda: f64, dones: &[bool]) -> Vec<f64> {
+	let n = rewards.len();
+	assert_eq!(dones.len(), n);
+	assert!(values.len() == n || values.len() == n + 1,
+			"expected {} or {} values, got {}", n, n + 1, values.len());
+
+	let mut ret = vec![0.; n];
+	let mut running = 0.;
+	for t in (0..n).rev() {
+		let (next_value, carry) = if dones[t] {
+			(0., 0.)
+		} else {
+			(values.get(t + 1).cloned().unwrap_or(0.), running)
+		};
+		let delta = rewards[t] + gamma*next_value - values[t];
+		running = delta + gamma*lambda*carry;
+		ret[t] = running;
+	}
+	ret
+}
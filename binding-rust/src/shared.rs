@@ -0,0 +1,51 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use {Environment, GymClient, GymResult, State};
+
+/// An `Environment` that several threads can step. Each reset or step holds
+/// a lock for the whole request and response, so calls from different
+/// threads never interleave on the connection, and their combined
+/// throughput is that of a single environment. Use a `ClientPool` to make
+/// one environment per thread instead when they should run in parallel.
+#[derive(Clone)]
+pub struct SharedEnvironment {
+	env:	Arc<Mutex<Environment>>,
+}
+
+impl SharedEnvironment {
+	pub fn new(env: Environment) -> SharedEnvironment {
+		SharedEnvironment{env: Arc::new(Mutex::new(env))}
+	}
+	pub fn reset(&self) -> GymResult<Vec<f64>> {
+		self.lock().reset()
+	}
+	pub fn step(&self, action: Vec<f64>, render: bool) -> GymResult<State> {
+		self.lock().step(action, render)
+	}
+	/// Locks the environment for any other calls, e.g. several steps that
+	/// must not be interleaved with other threads'.
+	pub fn lock<'a>(&'a self) -> MutexGuard<'a, Environment> {
+		// A panic in another thread leaves the environment as usable as a
+		// failed request does.
+		self.env.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+/// Hands out independent clients, each with its own connections, that share
+/// one configuration. Can be shared between threads by reference.
+pub struct ClientPool {
+	template:	Mutex<GymClient>,
+}
+
+impl ClientPool {
+	pub fn new(client: GymClient) -> ClientPool {
+		ClientPool{template: Mutex::new(client)}
+	}
+	pub fn client(&self) -> GymClient {
+		self.template.lock().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+	/// Makes an environment on a client of its own.
+	pub fn make(&self, env_id: &str) -> GymResult<Environment> {
+		self.client().make(env_id)
+	}
+}
@@ -0,0 +1,182 @@
+use std::io::{BufReader, Read};
+
+use hyper;
+use serde::de::{Deserialize, Deserializer, Error, MapVisitor, SeqVisitor, Visitor};
+use serde::de::impls::IgnoredAny;
+use serde_json::{self, Value};
+
+use {GymError, GymResult};
+use base64;
+
+// A step response read straight off the connection by
+// `Environment::step_streaming`. The observation goes into a `Vec<f64>` as
+// the numbers arrive, so neither the body nor the observation as a `Value`
+// is in memory at any point.
+pub struct StreamedStep {
+	pub observation:	Vec<f64>,
+	pub reward:			f64,
+	pub done:			bool,
+	pub truncated:		bool,
+	pub info:			Value,
+	// Every key of the response, for strict mode.
+	pub keys:			Vec<String>,
+}
+
+// The reward and done flag of a step response, read by
+// `Environment::step_reward_only`. The observation and info are skipped over
+// without being parsed into anything.
+pub struct RewardOnly {
+	pub reward:	f64,
+	pub done:	bool,
+	pub keys:	Vec<String>,
+}
+
+// Reads a `StreamedStep` or a `RewardOnly`. A connection that breaks
+// mid-response gives `GymError::Connection`.
+pub fn read_step<T: Deserialize, R: Read>(body: R) -> GymResult<T> {
+	let mut de = serde_json::Deserializer::new(BufReader::new(body).bytes());
+	T::deserialize(&mut de)
+		.and_then(|step| de.end().map(|_| step))
+		.map_err(|e| match e {
+			serde_json::Error::Io(e) => GymError::Connection(hyper::Error::Io(e)),
+			e => GymError::Schema(format!("could not parse the step response: {}", e))
+		})
+}
+
+// The end of an episode, as `done` or as gym 0.26's `terminated` and
+// `truncated`, like `parse_done` reads it from a `Value`.
+#[derive(Default)]
+struct Done {
+	done:		Option<bool>,
+	terminated:	Option<bool>,
+	truncated:	Option<bool>,
+}
+
+impl Done {
+	// Whether the episode is over, and whether it was cut short.
+	fn get(&self) -> Option<(bool, bool)> {
+		match (self.done, self.terminated, self.truncated) {
+			(Some(done), _, truncated) => Some((done, truncated.unwrap_or(false))),
+			(None, Some(terminated), Some(truncated)) => Some((terminated || truncated, truncated)),
+			_ => None
+		}
+	}
+}
+
+// The observations of a Discrete space come as a bare integer.
+struct Floats(Vec<f64>);
+
+impl Deserialize for Floats {
+	fn deserialize<D: Deserializer>(deserializer: &mut D) -> Result<Floats, D::Error> {
+		deserializer.deserialize(FloatsVisitor)
+	}
+}
+
+struct FloatsVisitor;
+
+impl Visitor for FloatsVisitor {
+	type Value = Floats;
+
+	fn visit_seq<V: SeqVisitor>(&mut self, mut visitor: V) -> Result<Floats, V::Error> {
+		let mut floats = Vec::with_capacity(visitor.size_hint().0);
+		while let Some(x) = try!(visitor.visit::<f64>()) {
+			floats.push(x);
+		}
+		try!(visitor.end());
+		Ok(Floats(floats))
+	}
+	fn visit_i64<E: Error>(&mut self, x: i64) -> Result<Floats, E> {
+		Ok(Floats(vec![x as f64]))
+	}
+	fn visit_u64<E: Error>(&mut self, x: u64) -> Result<Floats, E> {
+		Ok(Floats(vec![x as f64]))
+	}
+	fn visit_f64<E: Error>(&mut self, x: f64) -> Result<Floats, E> {
+		Ok(Floats(vec![x]))
+	}
+	// Base64 bytes, as some servers send images.
+	fn visit_str<E: Error>(&mut self, encoded: &str) -> Result<Floats, E> {
+		match base64::decode(encoded) {
+			Some(bytes) => Ok(Floats(bytes.into_iter().map(|b| b as f64).collect())),
+			None => Err(E::custom("expected base64 bytes as the observation"))
+		}
+	}
+}
+
+impl Deserialize for StreamedStep {
+	fn deserialize<D: Deserializer>(deserializer: &mut D) -> Result<StreamedStep, D::Error> {
+		deserializer.deserialize_map(StepVisitor)
+	}
+}
+
+struct StepVisitor;
+
+impl Visitor for StepVisitor {
+	type Value = StreamedStep;
+
+	fn visit_map<V: MapVisitor>(&mut self, mut visitor: V) -> Result<StreamedStep, V::Error> {
+		let (mut observation, mut reward, mut info) = (None, None, None);
+		let mut done = Done::default();
+		let mut keys = Vec::new();
+		while let Some(key) = try!(visitor.visit_key::<String>()) {
+			match &key[..] {
+				"observation" => observation = Some(try!(visitor.visit_value::<Floats>()).0),
+				"reward" => reward = Some(try!(visitor.visit_value())),
+				"done" => done.done = Some(try!(visitor.visit_value())),
+				"terminated" => done.terminated = Some(try!(visitor.visit_value())),
+				"truncated" => done.truncated = Some(try!(visitor.visit_value())),
+				"info" => info = Some(try!(visitor.visit_value())),
+				_ => {
+					try!(visitor.visit_value::<IgnoredAny>());
+				}
+			}
+			keys.push(key);
+		}
+		try!(visitor.end());
+		let (done, truncated) = try!(done.get().map_or_else(|| visitor.missing_field("done"), Ok));
+		Ok(StreamedStep {
+			observation: try!(observation.map_or_else(|| visitor.missing_field("observation"), Ok)),
+			reward: try!(reward.map_or_else(|| visitor.missing_field("reward"), Ok)),
+			done: done,
+			truncated: truncated,
+			info: try!(info.map_or_else(|| visitor.missing_field("info"), Ok)),
+			keys: keys
+		})
+	}
+}
+
+impl Deserialize for RewardOnly {
+	fn deserialize<D: Deserializer>(deserializer: &mut D) -> Result<RewardOnly, D::Error> {
+		deserializer.deserialize_map(RewardOnlyVisitor)
+	}
+}
+
+struct RewardOnlyVisitor;
+
+impl Visitor for RewardOnlyVisitor {
+	type Value = RewardOnly;
+
+	fn visit_map<V: MapVisitor>(&mut self, mut visitor: V) -> Result<RewardOnly, V::Error> {
+		let mut reward = None;
+		let mut done = Done::default();
+		let mut keys = Vec::new();
+		while let Some(key) = try!(visitor.visit_key::<String>()) {
+			match &key[..] {
+				"reward" => reward = Some(try!(visitor.visit_value())),
+				"done" => done.done = Some(try!(visitor.visit_value())),
+				"terminated" => done.terminated = Some(try!(visitor.visit_value())),
+				"truncated" => done.truncated = Some(try!(visitor.visit_value())),
+				_ => {
+					try!(visitor.visit_value::<IgnoredAny>());
+				}
+			}
+			keys.push(key);
+		}
+		try!(visitor.end());
+		Ok(RewardOnly {
+			reward: try!(reward.map_or_else(|| visitor.missing_field("reward"), Ok)),
+			done: try!(done.get().map_or_else(|| visitor.missing_field("done"), Ok)).0,
+			keys: keys
+		})
+	}
+}
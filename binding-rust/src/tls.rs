@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use hyper;
+use hyper::client::{Client, Pool, ProxyConfig};
+use hyper::net::{HttpsConnector, NetworkStream, OpensslClient, SslClient};
+
+use openssl::ssl::{Ssl, SslContext, SslMethod, SslStream};
+use openssl::ssl::{SSL_VERIFY_NONE, SSL_VERIFY_PEER, SSL_OP_NO_SSLV2, SSL_OP_NO_SSLV3, SSL_OP_NO_COMPRESSION};
+use openssl_verify::verify_callback;
+
+use connector::NoDelayConnector;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsConfig {
+	pub verify:			bool,
+	pub ca_cert_path:	Option<PathBuf>,
+}
+
+impl Default for TlsConfig {
+	fn default() -> TlsConfig {
+		TlsConfig{verify: true, ca_cert_path: None}
+	}
+}
+
+impl TlsConfig {
+	pub fn client(&self, proxy: Option<(String, u16)>, nodelay: bool) -> hyper::Result<Client> {
+		let connector = NoDelayConnector{nodelay: nodelay};
+		if *self == TlsConfig::default() {
+			return Ok(match proxy {
+				Some((host, port)) => Client::with_http_proxy(host, port),
+				None => Client::with_connector(Pool::with_connector(Default::default(),
+					HttpsConnector::with_connector(OpensslClient::default(), connector)))
+			});
+		}
+
+		let mut ctx = try!(SslContext::new(SslMethod::Sslv23));
+		try!(ctx.set_default_verify_paths());
+		if let Some(ref path) = self.ca_cert_path {
+			try!(ctx.set_CA_file(path));
+		}
+		ctx.set_options(SSL_OP_NO_SSLV2 | SSL_OP_NO_SSLV3 | SSL_OP_NO_COMPRESSION);
+
+		let ssl = Connector{ctx: ctx, verify: self.verify};
+		Ok(match proxy {
+			Some((host, port)) => Client::with_proxy_config(ProxyConfig(host, port, ssl)),
+			None => Client::with_connector(Pool::with_connector(Default::default(), HttpsConnector::with_connector(ssl, connector)))
+		})
+	}
+}
+
+struct Connector {
+	ctx:	SslContext,
+	verify:	bool,
+}
+
+impl<T: NetworkStream + Send + Clone> SslClient<T> for Connector {
+	type Stream = SslStream<T>;
+
+	fn wrap_client(&self, stream: T, host: &str) -> hyper::Result<SslStream<T>> {
+		let mut ssl = try!(Ssl::new(&self.ctx));
+		try!(ssl.set_hostname(host));
+		if self.verify {
+			let host = host.to_owned();
+			ssl.set_verify_callback(SSL_VERIFY_PEER, move |p, x| verify_callback(&host, p, x));
+		} else {
+			ssl.set_verify(SSL_VERIFY_NONE);
+		}
+		SslStream::connect(ssl, stream).map_err(From::from)
+	}
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use serde_json::Value;
+use serde_json::value::{ToJson, from_value};
+
+use RunningNormalizer;
+
+/// The steps of a rollout, stored as parallel arrays. `observations[t]` is the
+/// observation `actions[t]` was taken in; `rewards[t]`, `dones[t]`,
+/// `truncated[t]` and `infos[t]` come from the step that action triggered.
+/// `truncated[t]` marks done steps that cut the episode short, e.g. at a time
+/// limit, rather than ending it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trajectory {
+	pub observations:	Vec<Vec<f64>>,
+	pub actions:		Vec<Vec<f64>>,
+	pub rewards:		Vec<f64>,
+	pub dones:			Vec<bool>,
+	pub truncated:		Vec<bool>,
+	pub infos:			Vec<Value>,
+}
+
+impl Trajectory {
+	pub fn new() -> Trajectory {
+		Trajectory::default()
+	}
+	pub fn push_step(&mut self, observation: Vec<f64>, action: Vec<f64>, reward: f64, done: bool, info: Value) {
+		self.observations.push(observation);
+		self.actions.push(action);
+		self.rewards.push(reward);
+		self.dones.push(done);
+		self.truncated.push(false);
+		self.infos.push(info);
+	}
+	/// Like `push_step`, for a step that was done because the episode was
+	/// truncated.
+	pub fn push_truncated_step(&mut self, observation: Vec<f64>, action: Vec<f64>, reward: f64, info: Value) {
+		self.push_step(observation, action, reward, true, info);
+		if let Some(truncated) = self.truncated.last_mut() {
+			*truncated = true;
+		}
+	}
+	pub fn len(&self) -> usize {
+		self.rewards.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.rewards.is_empty()
+	}
+	pub fn total_reward(&self) -> f64 {
+		self.rewards.iter().sum()
+	}
+	/// Per-dimension minimum, maximum, mean and (population) standard
+	/// deviation of the observations, to compare with the declared bounds of
+	/// the space. The mean and variance are accumulated with Welford's
+	/// algorithm, as by `RunningNormalizer`. All four are empty for an empty
+	/// trajectory. Panics if the observations differ in length.
+	pub fn observation_stats(&self) -> (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>) {
+		let dim = self.observations.first().map_or(0, |o| o.len());
+		let mut min = vec![std::f64::INFINITY; dim];
+		let mut max = vec![std::f64::NEG_INFINITY; dim];
+		let mut moments = RunningNormalizer::new(dim);
+		for observation in &self.observations {
+			moments.update(observation);
+			for (i, &x) in observation.iter().enumerate() {
+				min[i] = min[i].min(x);
+				max[i] = max[i].max(x);
+			}
+		}
+		let std = moments.variance().into_iter().map(f64::sqrt).collect();
+		(min, max, moments.mean().to_vec(), std)
+	}
+	/// Inverse of `to_json`. Returns `None` if a field is missing or malformed.
+	/// `truncated` may be missing, as in JSON written before it existed, and
+	/// is then false for every step.
+	pub fn from_json(json: &Value) -> Option<Trajectory> {
+		macro_rules! field {
+			($key:expr) => {
+				match json.find($key).and_then(|v| from_value(v.clone()).ok()) {
+					Some(v) => v,
+					None => return None
+				}
+			}
+		}
+		let dones: Vec<bool> = field!("dones");
+		let trajectory = Trajectory {
+			observations: field!("observations"),
+			actions: field!("actions"),
+			rewards: field!("rewards"),
+			truncated: match json.find("truncated") {
+				Some(_) => field!("truncated"),
+				None => vec![false; dones.len()]
+			},
+			dones: dones,
+			infos: match json.find("infos").and_then(|v| v.as_array()) {
+				Some(infos) => infos.clone(),
+				None => return None
+			},
+		};
+		let n = trajectory.rewards.len();
+		if trajectory.observations.len() != n || trajectory.actions.len() != n
+			|| trajectory.dones.len() != n || trajectory.truncated.len() != n || trajectory.infos.len() != n {
+			return None;
+		}
+		Some(trajectory)
+	}
+	/// Writes one CSV row per step: `step,reward,done`, followed by one column
+	/// per observation element (`obs_0`, ...) and action element (`action_0`,
+	/// ...). Column counts are taken from the first step. Infos are not written.
+	pub fn to_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		let obs_dim = self.observations.first().map_or(0, |o| o.len());
+		let act_dim = self.actions.first().map_or(0, |a| a.len());
+
+		let mut header = vec!["step".to_string(), "reward".to_string(), "done".to_string()];
+		header.extend((0..obs_dim).map(|i| format!("obs_{}", i)));
+		header.extend((0..act_dim).map(|i| format!("action_{}", i)));
+		try!(writeln!(writer, "{}", header.join(",")));
+
+		for t in 0..self.len() {
+			let mut row = vec![t.to_string(), self.rewards[t].to_string(), self.dones[t].to_string()];
+			row.extend(self.observations[t].iter().map(|x| x.to_string()));
+			row.extend(self.actions[t].iter().map(|x| x.to_string()));
+			try!(writeln!(writer, "{}", row.join(",")));
+		}
+		Ok(())
+	}
+	/// Converts to the step layout of RLDS datasets:
+	///
+	/// ```text
+	/// {"steps": [{"observation": [f64], "action": [f64], "reward": f64,
+	///             "discount": f64, "is_first": bool, "is_last": bool,
+	///             "is_terminal": bool}, ...]}
+	/// ```
+	///
+	/// A step is first when it starts the trajectory or follows a done step,
+	/// and last when it is done or ends the trajectory. It is terminal only
+	/// when it is done and not truncated, since a truncated episode could have
+	/// gone on. `discount` is 0 on terminal steps and 1 otherwise.
+	pub fn to_rlds_json(&self) -> Value {
+		let steps: Vec<Value> = (0..self.len()).map(|t| {
+			let terminal = self.dones[t] && !self.truncated[t];
+			let mut step = BTreeMap::new();
+			step.insert("observation".to_string(), self.observations[t].to_json());
+			step.insert("action".to_string(), self.actions[t].to_json());
+			step.insert("reward".to_string(), Value::F64(self.rewards[t]));
+			step.insert("discount".to_string(), Value::F64(if terminal { 0. } else { 1. }));
+			step.insert("is_first".to_string(), Value::Bool(t == 0 || self.dones[t - 1]));
+			step.insert("is_last".to_string(), Value::Bool(self.dones[t] || t + 1 == self.len()));
+			step.insert("is_terminal".to_string(), Value::Bool(terminal));
+			Value::Object(step)
+		}).collect();
+		let mut json = BTreeMap::new();
+		json.insert("steps".to_string(), Value::Array(steps));
+		Value::Object(json)
+	}
+}
+
+impl ToJson for Trajectory {
+	fn to_json(&self) -> Value {
+		let mut json = BTreeMap::new();
+		json.insert("observations".to_string(), self.observations.to_json());
+		json.insert("actions".to_string(), self.actions.to_json());
+		json.insert("rewards".to_string(), self.rewards.to_json());
+		json.insert("dones".to_string(), self.dones.to_json());
+		json.insert("truncated".to_string(), self.truncated.to_json());
+		json.insert("infos".to_string(), Value::Array(self.infos.clone()));
+		Value::Object(json)
+	}
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use serde_json::value::ToJson;
+
+use {Environment, GymClient, GymError, GymResult, State, StepRequest};
+
+const BATCH_ROUTE: &'static str = "/v1/batch/step/";
+
+/// Several environments stepped together. Servers that answer
+/// `POST /v1/batch/step/` take all the actions in one request:
+///
+/// ```text
+/// {"instance_ids": [id, ...], "actions": [action, ...], "render": bool}
+/// ```
+///
+/// and answer with `{"results": [step response, ...]}` in the same order. The
+/// reference server has no such route, so after the first step answered with
+/// 404 the environments are stepped one after the other, each over its own
+/// connection. `batched` tells which path is taken.
+pub struct VectorEnvironment {
+	envs:		Vec<Environment>,
+	batched:	Option<bool>,
+}
+
+impl VectorEnvironment {
+	/// The environments must live on the same server.
+	pub fn new(envs: Vec<Environment>) -> VectorEnvironment {
+		assert!(!envs.is_empty(), "a VectorEnvironment needs at least one environment");
+		VectorEnvironment{envs: envs, batched: None}
+	}
+	pub fn num_envs(&self) -> usize {
+		self.envs.len()
+	}
+	pub fn envs(&self) -> &[Environment] {
+		&self.envs
+	}
+	pub fn envs_mut(&mut self) -> &mut [Environment] {
+		&mut self.envs
+	}
+	/// Whether steps go out in one batched request, or `None` before the
+	/// first step has found out.
+	pub fn batched(&self) -> Option<bool> {
+		self.batched
+	}
+	pub fn reset(&mut self) -> GymResult<Vec<Vec<f64>>> {
+		self.envs.iter_mut().map(|env| env.reset()).collect()
+	}
+	/// Takes `actions[i]` in the `i`th environment.
+	pub fn step(&mut self, actions: Vec<Vec<f64>>, render: bool) -> GymResult<Vec<State>> {
+		assert_eq!(actions.len(), self.envs.len(), "expected one action per environment");
+		if self.batched != Some(false) && !self.envs[0].client.dry_run {
+			match self.step_batched(&actions, render) {
+				Ok(states) => {
+					self.batched = Some(true);
+					return Ok(states);
+				},
+				Err(GymError::Server{status: 404, ..}) | Err(GymError::NonJsonResponse{status: 404, ..}) if self.batched.is_none() =>
+					self.batched = Some(false),
+				Err(e) => return Err(e)
+			}
+		}
+		actions.into_iter().zip(&mut self.envs).map(|(action, env)| env.step(action, render)).collect()
+	}
+	fn step_batched(&mut self, actions: &[Vec<f64>], render: bool) -> GymResult<Vec<State>> {
+		let mut instance_ids = Vec::with_capacity(actions.len());
+		let mut json_actions = Vec::with_capacity(actions.len());
+		for (env, action) in self.envs.iter_mut().zip(actions) {
+			let mut req = try!(env.step_body(StepRequest::new(action.clone()).render(render)));
+			try!(env.prepare_step());
+			instance_ids.push(Value::String(env.instance_id.clone()));
+			json_actions.push(req.remove("action").unwrap());
+		}
+		let mut req = BTreeMap::new();
+		req.insert("instance_ids", Value::Array(instance_ids));
+		req.insert("actions", Value::Array(json_actions));
+		req.insert("render", Value::Bool(render));
+
+		let response = try!(self.envs[0].client.post(BATCH_ROUTE.to_string(), req.to_json()));
+		let results = match response.find("results").and_then(|results| results.as_array()) {
+			Some(results) if results.len() == self.envs.len() => results.clone(),
+			_ => return Err(GymError::Schema(format!("expected {} results from {}", self.envs.len(), BATCH_ROUTE)))
+		};
+		let mut states = Vec::with_capacity(results.len());
+		for (env, result) in self.envs.iter_mut().zip(results) {
+			states.push(try!(env.finish_step(result)));
+		}
+		Ok(states)
+	}
+}
+
+impl GymClient {
+	/// Makes `n` environments of `env_id`, each on a copy of this client.
+	pub fn make_vector(self, env_id: &str, n: usize) -> GymResult<VectorEnvironment> {
+		let factory = self.env_factory(env_id);
+		let envs: Vec<_> = try!((0..n).map(|_| factory.create()).collect());
+		Ok(VectorEnvironment::new(envs))
+	}
+}
@@ -0,0 +1,76 @@
+use {Environment, GymClient, GymResult, ObsPipeline, State};
+
+/// A wrapper for `GymClient::make_wrapped`. The reference server cannot wrap
+/// environments itself, so these are applied on the client.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WrapperSpec {
+	/// Ends episodes after this many steps, as `set_max_episode_steps` does.
+	TimeLimit(u32),
+	/// Stacks the last frames of the observation, as `ObsPipeline::stack`.
+	FrameStack(usize),
+	/// Clamps observations into the Box bounds, as `ObsPipeline::clamp`.
+	ClampObservation,
+	/// Rescales observations to `[0, 1]`, as `ObsPipeline::normalize`.
+	NormalizeObservation,
+	/// One-hot encodes Discrete observations, as `ObsPipeline::one_hot`.
+	OneHot,
+}
+
+/// An environment whose observations go through an `ObsPipeline`. The
+/// pipeline's frame history is cleared whenever an episode ends.
+pub struct WrappedEnvironment {
+	env:		Environment,
+	pipeline:	ObsPipeline,
+}
+
+impl WrappedEnvironment {
+	pub fn new(env: Environment, pipeline: ObsPipeline) -> WrappedEnvironment {
+		WrappedEnvironment{env: env, pipeline: pipeline}
+	}
+	pub fn reset(&mut self) -> GymResult<Vec<f64>> {
+		let mut observation = try!(self.env.reset());
+		self.pipeline.reset();
+		self.pipeline.apply(&mut observation, self.env.observation_space());
+		Ok(observation)
+	}
+	/// With auto-reset on, the observation of a final step already belongs to
+	/// the next episode and starts its frame history.
+	pub fn step(&mut self, action: Vec<f64>, render: bool) -> GymResult<State> {
+		let mut state = try!(self.env.step(action, render));
+		if state.done && self.env.auto_reset {
+			self.pipeline.reset();
+		}
+		self.pipeline.apply(&mut state.observation, self.env.observation_space());
+		Ok(state)
+	}
+	pub fn env(&self) -> &Environment {
+		&self.env
+	}
+	pub fn env_mut(&mut self) -> &mut Environment {
+		&mut self.env
+	}
+	pub fn into_inner(self) -> Environment {
+		self.env
+	}
+}
+
+impl GymClient {
+	/// Makes an environment with `wrappers` applied in order.
+	pub fn make_wrapped(self, env_id: &str, wrappers: Vec<WrapperSpec>) -> GymResult<WrappedEnvironment> {
+		let mut env = try!(self.make(env_id));
+		let mut pipeline = ObsPipeline::new();
+		for wrapper in wrappers {
+			pipeline = match wrapper {
+				WrapperSpec::TimeLimit(steps) => {
+					env.set_max_episode_steps(steps);
+					pipeline
+				},
+				WrapperSpec::FrameStack(frames) => pipeline.stack(frames),
+				WrapperSpec::ClampObservation => pipeline.clamp(),
+				WrapperSpec::NormalizeObservation => pipeline.normalize(),
+				WrapperSpec::OneHot => pipeline.one_hot()
+			};
+		}
+		Ok(WrappedEnvironment::new(env, pipeline))
+	}
+}
@@ -0,0 +1,88 @@
+//! End-to-end tests against the reference python server. They only run when
+//! `GYM_SERVER_TESTS` is set, and are skipped when the server cannot be
+//! started (e.g. python or gym is not installed).
+
+extern crate gym;
+
+use std::env;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use gym::*;
+
+struct Server {
+	process:	Child,
+	address:	String,
+}
+
+impl Server {
+	fn start() -> Option<Server> {
+		if env::var_os("GYM_SERVER_TESTS").is_none() {
+			println!("GYM_SERVER_TESTS is not set, skipping");
+			return None;
+		}
+
+		let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+		let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("gym_http_server.py");
+		let python = env::var("PYTHON").unwrap_or("python3".to_string());
+		let process = match Command::new(python).arg(script).arg("-p").arg(port.to_string())
+												.stdout(Stdio::null()).stderr(Stdio::null())
+												.spawn() {
+			Ok(process) => process,
+			Err(e) => {
+				println!("could not launch the gym server ({}), skipping", e);
+				return None;
+			}
+		};
+
+		let mut server = Server{process: process, address: format!("http://127.0.0.1:{}", port)};
+		let start = Instant::now();
+		while start.elapsed() < Duration::from_secs(30) {
+			if let Ok(Some(status)) = server.process.try_wait() {
+				println!("gym server exited with {}, skipping", status);
+				return None;
+			}
+			if server.client().ping().is_ok() {
+				return Some(server);
+			}
+			thread::sleep(Duration::from_millis(100));
+		}
+		println!("gym server did not come up, skipping");
+		None
+	}
+	fn client(&self) -> GymClient {
+		GymClient::new(self.address.clone())
+	}
+}
+
+impl Drop for Server {
+	fn drop(&mut self) {
+		let _ = self.process.kill();
+		let _ = self.process.wait();
+	}
+}
+
+#[test]
+fn test_cartpole_cycle() {
+	let server = match Server::start() {
+		Some(server) => server,
+		None => return
+	};
+
+	let mut env = server.client().make("CartPole-v0").unwrap();
+	let observation = env.reset().unwrap();
+	assert_eq!(observation.len(), env.observation_space().flat_dim());
+
+	loop {
+		let action = env.action_space().sample();
+		let state = env.step(action, false).unwrap();
+		assert_eq!(state.observation.len(), env.observation_space().flat_dim());
+		if state.done {
+			break;
+		}
+	}
+	env.close().unwrap();
+}
@@ -111,8 +111,19 @@ class Envs(object):
         info['name'] = space.__class__.__name__
         if info['name'] == 'Discrete':
             info['n'] = space.n
+        elif info['name'] == 'MultiDiscrete':
+            # Before nvec, gym's MultiDiscrete took inclusive [low, high] ranges.
+            low = getattr(space, 'low', None)
+            nvec = getattr(space, 'nvec', None)
+            if nvec is None:
+                nvec = np.array(space.high) - np.array(low) + 1
+            if low is None:
+                low = np.zeros_like(nvec)
+            info['nvec'] = [int(x) for x in np.array(nvec).flatten()]
+            info['low'] = [int(x) for x in np.array(low).flatten()]
         elif info['name'] == 'Box':
             info['shape'] = space.shape
+            info['dtype'] = str(getattr(space, 'dtype', 'float32'))
             # It's not JSON compliant to have Infinity, -Infinity, NaN.
             # Many newer JSON parsers allow it, but many don't. Notably python json
             # module can read and write such floats. So we only here fix "export version",